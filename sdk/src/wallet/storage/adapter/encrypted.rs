@@ -0,0 +1,132 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use aes_gcm_siv::{
+    aead::{Aead, KeyInit},
+    Aes256GcmSiv, Nonce,
+};
+use std::fmt;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use crypto::{keys::pbkdf::PBKDF2_HMAC_SHA256, utils::rand::fill};
+
+use super::StorageAdapter;
+use crate::wallet::Error;
+
+/// Marker written in front of every encrypted record so that legacy plaintext
+/// stores (written before this wrapper existed) keep loading unchanged.
+const MAGIC: &[u8] = b"AESSIV1:";
+/// Length of the randomly generated per-write nonce, in bytes (96 bit).
+const NONCE_LENGTH: usize = 12;
+/// Length of the derived content-encryption key, in bytes (256 bit).
+const KEY_LENGTH: usize = 32;
+/// PBKDF2 iteration count used to stretch the wallet password into the key.
+const PBKDF_ITERATIONS: u32 = 100_000;
+
+/// Storage adapter that transparently encrypts every value before handing it to
+/// the wrapped adapter and decrypts it on the way back out.
+///
+/// AES-GCM-SIV is used rather than plain AES-GCM on purpose: two browser tabs can
+/// write the same key concurrently and `localStorage` offers no reliable monotonic
+/// counter, so a random nonce may be reused. The synthetic-IV construction degrades
+/// gracefully under such reuse — it leaks only that two records under the same key and
+/// nonce hold identical plaintext, rather than collapsing confidentiality entirely the
+/// way plain GCM does.
+pub struct EncryptedAdapter<S: StorageAdapter> {
+    inner: S,
+    key: Key,
+}
+
+/// Owns the derived content-encryption key and overwrites it on drop, so the raw key
+/// material does not linger in freed memory after the adapter goes away.
+struct Key([u8; KEY_LENGTH]);
+
+impl Drop for Key {
+    fn drop(&mut self) {
+        // `write_volatile` keeps the optimizer from eliding the wipe as a dead store.
+        for byte in &mut self.0 {
+            unsafe { core::ptr::write_volatile(byte, 0) };
+        }
+    }
+}
+
+// Manual `Debug` so the derived key is never rendered by `{:?}` into a debug log.
+impl<S: StorageAdapter> fmt::Debug for EncryptedAdapter<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EncryptedAdapter")
+            .field("key", &"<redacted>")
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: StorageAdapter> EncryptedAdapter<S> {
+    /// Wraps `inner`, deriving the content-encryption key from the wallet `password`
+    /// and `salt` via PBKDF2-HMAC-SHA256.
+    pub fn new(inner: S, password: &[u8], salt: &[u8]) -> Self {
+        let mut key = [0u8; KEY_LENGTH];
+        PBKDF2_HMAC_SHA256(password, salt, PBKDF_ITERATIONS, &mut key);
+        Self { inner, key: Key(key) }
+    }
+
+    /// Encodes `plaintext` as `MAGIC || base64(nonce || ciphertext || tag)`.
+    fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, Error> {
+        let cipher = Aes256GcmSiv::new_from_slice(&self.key.0).map_err(|_| Error::StorageEncryption)?;
+
+        let mut nonce = [0u8; NONCE_LENGTH];
+        fill(&mut nonce).map_err(|_| Error::StorageEncryption)?;
+
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), plaintext)
+            .map_err(|_| Error::StorageEncryption)?;
+
+        let mut payload = Vec::with_capacity(NONCE_LENGTH + ciphertext.len());
+        payload.extend_from_slice(&nonce);
+        payload.extend_from_slice(&ciphertext);
+
+        let mut out = Vec::from(MAGIC);
+        out.extend_from_slice(STANDARD.encode(payload).as_bytes());
+        Ok(out)
+    }
+
+    /// Reverses [`encrypt`](Self::encrypt), verifying the authentication tag.
+    fn decrypt(&self, stored: &[u8]) -> Result<Vec<u8>, Error> {
+        let cipher = Aes256GcmSiv::new_from_slice(&self.key.0).map_err(|_| Error::StorageDecryption)?;
+
+        let payload = STANDARD
+            .decode(&stored[MAGIC.len()..])
+            .map_err(|_| Error::StorageDecryption)?;
+
+        if payload.len() < NONCE_LENGTH {
+            return Err(Error::StorageDecryption);
+        }
+        let (nonce, ciphertext) = payload.split_at(NONCE_LENGTH);
+
+        cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| Error::StorageDecryption)
+    }
+}
+
+#[async_trait::async_trait]
+impl<S: StorageAdapter<Error = crate::wallet::Error>> StorageAdapter for EncryptedAdapter<S> {
+    type Error = crate::wallet::Error;
+
+    /// Gets the record associated with the given key and decrypts it. Records
+    /// without the [`MAGIC`] prefix are returned verbatim as legacy plaintext.
+    async fn get_bytes(&self, key: &str) -> Result<Option<Vec<u8>>, Self::Error> {
+        match self.inner.get_bytes(key).await? {
+            Some(stored) if stored.starts_with(MAGIC) => Ok(Some(self.decrypt(&stored)?)),
+            other => Ok(other),
+        }
+    }
+
+    /// Encrypts the record and saves or updates it on the wrapped storage.
+    async fn set_bytes(&self, key: &str, record: &[u8]) -> Result<(), Self::Error> {
+        self.inner.set_bytes(key, &self.encrypt(record)?).await
+    }
+
+    /// Removes a record from the wrapped storage.
+    async fn delete(&self, key: &str) -> crate::wallet::Result<()> {
+        self.inner.delete(key).await
+    }
+}
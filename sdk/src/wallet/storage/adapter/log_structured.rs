@@ -0,0 +1,259 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+use super::StorageAdapter;
+use crate::wallet::Error;
+
+/// Default number of operations written before a full checkpoint is taken and the
+/// superseded operation entries are pruned.
+pub const KEEP_STATE_EVERY: u64 = 100;
+
+/// Suffix of the blob holding the most recent full checkpoint of a logical key.
+const CKPT_SUFFIX: &str = ".ckpt";
+/// Suffix of the small record tracking the checkpoint and latest sequence numbers.
+const META_SUFFIX: &str = ".meta";
+/// Infix of an individual operation entry (`<key>.op.<seq>`).
+const OP_INFIX: &str = ".op.";
+
+/// Per-key log bookkeeping: the sequence of the last checkpoint and of the latest
+/// operation. Serialized as the ASCII string `"<ckpt_seq>:<latest_seq>"` so it
+/// survives string-only backends such as [`WasmAdapter`](super::WasmAdapter).
+#[derive(Clone, Copy, Default)]
+struct Meta {
+    ckpt_seq: u64,
+    latest_seq: u64,
+}
+
+impl Meta {
+    fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        let text = std::str::from_utf8(bytes).map_err(|e| Error::Storage(format!("corrupt log meta: {e}")))?;
+        let (ckpt, latest) = text
+            .split_once(':')
+            .ok_or_else(|| Error::Storage("corrupt log meta: missing separator".to_string()))?;
+        Ok(Self {
+            ckpt_seq: ckpt.parse().map_err(|e| Error::Storage(format!("corrupt log meta: {e}")))?,
+            latest_seq: latest.parse().map_err(|e| Error::Storage(format!("corrupt log meta: {e}")))?,
+        })
+    }
+
+    fn serialize(&self) -> Vec<u8> {
+        format!("{}:{}", self.ckpt_seq, self.latest_seq).into_bytes()
+    }
+}
+
+/// A mutation encoded as the change relative to the preceding state: the lengths of the
+/// common prefix and suffix it shares with that state, plus the differing middle run.
+///
+/// Serialized as `"<prefix>:<suffix>:" || base64(middle)`, keeping the whole entry ASCII
+/// so it survives string-only backends. Because wallet records mutate in localized
+/// regions, the middle is typically a small fraction of the full record — which is the
+/// whole point of the log: op entries stay small even though the adapter above only ever
+/// sees full serialized records.
+struct Delta {
+    prefix: usize,
+    suffix: usize,
+    middle: Vec<u8>,
+}
+
+impl Delta {
+    /// Computes the delta turning `base` into `next`.
+    fn between(base: &[u8], next: &[u8]) -> Self {
+        let max = base.len().min(next.len());
+        let prefix = (0..max).take_while(|&i| base[i] == next[i]).count();
+        let suffix = (0..(max - prefix))
+            .take_while(|&i| base[base.len() - 1 - i] == next[next.len() - 1 - i])
+            .count();
+        Self {
+            prefix,
+            suffix,
+            middle: next[prefix..next.len() - suffix].to_vec(),
+        }
+    }
+
+    fn serialize(&self) -> Vec<u8> {
+        let mut out = format!("{}:{}:", self.prefix, self.suffix).into_bytes();
+        out.extend_from_slice(STANDARD.encode(&self.middle).as_bytes());
+        out
+    }
+
+    fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        let text = std::str::from_utf8(bytes).map_err(|e| Error::Storage(format!("corrupt log op: {e}")))?;
+        let mut parts = text.splitn(3, ':');
+        let err = || Error::Storage("corrupt log op: malformed delta".to_string());
+        let prefix = parts.next().ok_or_else(err)?.parse().map_err(|_| err())?;
+        let suffix = parts.next().ok_or_else(err)?.parse().map_err(|_| err())?;
+        let middle = STANDARD.decode(parts.next().ok_or_else(err)?).map_err(|_| err())?;
+        Ok(Self { prefix, suffix, middle })
+    }
+
+    /// Applies the delta to `base`, reconstructing `next`.
+    fn apply(&self, base: &[u8]) -> Result<Vec<u8>, Error> {
+        if self.prefix + self.suffix > base.len() {
+            return Err(Error::Storage("corrupt log op: delta exceeds base length".to_string()));
+        }
+        let mut out = Vec::with_capacity(self.prefix + self.middle.len() + self.suffix);
+        out.extend_from_slice(&base[..self.prefix]);
+        out.extend_from_slice(&self.middle);
+        out.extend_from_slice(&base[base.len() - self.suffix..]);
+        Ok(out)
+    }
+}
+
+/// Storage adapter implementing an append-only, log-structured write path on top of
+/// any other adapter.
+///
+/// Every mutation is written as a small operation entry keyed `"<key>.op.<seq>"` by a
+/// monotonically increasing sequence number. The adapter only ever receives full
+/// serialized records, so each op stores the [`Delta`] between the new record and the
+/// current state rather than the record itself, keeping the log entries small; only
+/// every `checkpoint_interval` operations (by default [`KEEP_STATE_EVERY`]) is a full
+/// checkpoint blob written and the superseded op entries deleted. This bounds per-mutation write cost against
+/// small-quota backends and gives crash consistency — a partial write loses only the
+/// last operation, not the whole record.
+#[derive(Debug)]
+pub struct LogStructuredAdapter<S: StorageAdapter> {
+    inner: S,
+    checkpoint_interval: u64,
+}
+
+impl<S: StorageAdapter<Error = crate::wallet::Error>> LogStructuredAdapter<S> {
+    /// Wraps `inner` with the default checkpoint interval of [`KEEP_STATE_EVERY`].
+    pub fn new(inner: S) -> Self {
+        Self::with_checkpoint_interval(inner, KEEP_STATE_EVERY)
+    }
+
+    /// Wraps `inner`, taking a checkpoint every `checkpoint_interval` operations.
+    /// An interval of `0` is treated as `1`, i.e. every write is checkpointed.
+    pub fn with_checkpoint_interval(inner: S, checkpoint_interval: u64) -> Self {
+        Self {
+            inner,
+            checkpoint_interval: checkpoint_interval.max(1),
+        }
+    }
+
+    fn ckpt_key(key: &str) -> String {
+        format!("{key}{CKPT_SUFFIX}")
+    }
+
+    fn meta_key(key: &str) -> String {
+        format!("{key}{META_SUFFIX}")
+    }
+
+    fn op_key(key: &str, seq: u64) -> String {
+        format!("{key}{OP_INFIX}{seq}")
+    }
+
+    async fn load_meta(&self, key: &str) -> Result<Option<Meta>, Error> {
+        self.inner
+            .get_bytes(&Self::meta_key(key))
+            .await?
+            .map(|bytes| Meta::parse(&bytes))
+            .transpose()
+    }
+
+    /// Reconstructs the current state of `key` from its checkpoint and op log, using
+    /// already-loaded `meta` to avoid a second meta read.
+    async fn replay(&self, key: &str, meta: Meta) -> Result<Option<Vec<u8>>, Error> {
+        let mut state = if meta.ckpt_seq > 0 {
+            self.inner.get_bytes(&Self::ckpt_key(key)).await?
+        } else {
+            None
+        };
+        for seq in (meta.ckpt_seq + 1)..=meta.latest_seq {
+            if let Some(op) = self.inner.get_bytes(&Self::op_key(key, seq)).await? {
+                let base = state.unwrap_or_default();
+                state = Some(Delta::parse(&op)?.apply(&base)?);
+            }
+        }
+        Ok(state)
+    }
+
+    /// Deletes every operation entry in `(prev_ckpt_seq, seq]`, i.e. all operations now
+    /// superseded by the checkpoint at `seq`.
+    async fn prune_ops(&self, key: &str, prev_ckpt_seq: u64, seq: u64) -> Result<(), Error> {
+        for stale in (prev_ckpt_seq + 1)..=seq {
+            self.inner.delete(&Self::op_key(key, stale)).await?;
+        }
+        Ok(())
+    }
+
+    /// Forces a checkpoint of the current state and prunes the operation log.
+    pub async fn compact(&self, key: &str) -> crate::wallet::Result<()> {
+        let Some(meta) = self.load_meta(key).await? else {
+            return Ok(());
+        };
+        if let Some(state) = self.replay(key, meta).await? {
+            // Fold the whole log into a checkpoint at the next sequence. The checkpoint
+            // blob and new meta are written before the log is pruned, so a crash before
+            // the prune only leaves superseded ops behind rather than losing the record.
+            let seq = meta.latest_seq + 1;
+            self.inner.set_bytes(&Self::ckpt_key(key), &state).await?;
+            let new_meta = Meta {
+                ckpt_seq: seq,
+                latest_seq: seq,
+            };
+            self.inner.set_bytes(&Self::meta_key(key), &new_meta.serialize()).await?;
+            self.prune_ops(key, meta.ckpt_seq, seq).await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl<S: StorageAdapter<Error = crate::wallet::Error>> StorageAdapter for LogStructuredAdapter<S> {
+    type Error = crate::wallet::Error;
+
+    /// Reconstructs the current state by loading the latest checkpoint and applying every
+    /// operation delta with a higher sequence. Keys without log metadata are delegated to
+    /// the wrapped adapter as legacy flat records.
+    async fn get_bytes(&self, key: &str) -> Result<Option<Vec<u8>>, Self::Error> {
+        let Some(meta) = self.load_meta(key).await? else {
+            return self.inner.get_bytes(key).await;
+        };
+        self.replay(key, meta).await
+    }
+
+    /// Appends the mutation as a new operation entry holding only the delta against the
+    /// current state, taking a checkpoint and pruning the log once
+    /// [`checkpoint_interval`](Self::with_checkpoint_interval) operations have accumulated
+    /// since the last one.
+    async fn set_bytes(&self, key: &str, record: &[u8]) -> Result<(), Self::Error> {
+        let mut meta = self.load_meta(key).await?.unwrap_or_default();
+        let current = self.replay(key, meta).await?.unwrap_or_default();
+        let seq = meta.latest_seq + 1;
+
+        let delta = Delta::between(&current, record);
+        self.inner.set_bytes(&Self::op_key(key, seq), &delta.serialize()).await?;
+        meta.latest_seq = seq;
+
+        let prev_ckpt_seq = meta.ckpt_seq;
+        let checkpointing = seq - meta.ckpt_seq >= self.checkpoint_interval;
+        if checkpointing {
+            self.inner.set_bytes(&Self::ckpt_key(key), record).await?;
+            meta.ckpt_seq = seq;
+        }
+
+        // Commit meta before pruning: if we crash before the prune the stale ops are
+        // simply ignored on load (they sit at or below `ckpt_seq`), whereas pruning first
+        // could erase the only copy of the record.
+        self.inner.set_bytes(&Self::meta_key(key), &meta.serialize()).await?;
+        if checkpointing {
+            self.prune_ops(key, prev_ckpt_seq, seq).await?;
+        }
+        Ok(())
+    }
+
+    /// Removes the checkpoint, metadata and every outstanding operation entry.
+    async fn delete(&self, key: &str) -> crate::wallet::Result<()> {
+        if let Some(meta) = self.load_meta(key).await? {
+            for seq in (meta.ckpt_seq + 1)..=meta.latest_seq {
+                self.inner.delete(&Self::op_key(key, seq)).await?;
+            }
+            self.inner.delete(&Self::ckpt_key(key)).await?;
+            self.inner.delete(&Self::meta_key(key)).await?;
+        }
+        self.inner.delete(key).await
+    }
+}
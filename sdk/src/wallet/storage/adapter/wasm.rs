@@ -8,7 +8,7 @@ use web_sys::{js_sys, wasm_bindgen::JsValue};
 
 use crate::wallet::Error;
 
-use super::StorageAdapter;
+use super::{compressed::CompressedAdapter, encrypted::EncryptedAdapter, StorageAdapter};
 
 /// The storage id.
 pub const STORAGE_ID: &str = "Wasm";
@@ -62,6 +62,38 @@ impl WasmAdapter {
         Ok(Self { key_prefix })
     }
 
+    /// Instantiates a [`WasmAdapter`] wrapped in a [`CompressedAdapter`], so stored
+    /// records are transparently DEFLATE-compressed before hitting the per-origin
+    /// `localStorage` quota. This is the recommended entry point for browser wallets;
+    /// legacy uncompressed records written by [`new`](Self::new) keep loading unchanged.
+    pub fn new_compressed(path: impl AsRef<Path>) -> crate::wallet::Result<CompressedAdapter<Self>> {
+        Ok(CompressedAdapter::new(Self::new(path)?))
+    }
+
+    /// Instantiates a [`WasmAdapter`] wrapped in an [`EncryptedAdapter`], so records are
+    /// encrypted at rest before they reach `localStorage` where any script in the origin
+    /// could otherwise read them. The key is derived from the wallet `password` and
+    /// `salt`; legacy plaintext records written by [`new`](Self::new) keep loading.
+    pub fn new_encrypted(
+        path: impl AsRef<Path>,
+        password: &[u8],
+        salt: &[u8],
+    ) -> crate::wallet::Result<EncryptedAdapter<Self>> {
+        Ok(EncryptedAdapter::new(Self::new(path)?, password, salt))
+    }
+
+    /// Instantiates a [`WasmAdapter`] that both compresses and encrypts at rest. The
+    /// compressor wraps the encryptor on purpose: records are DEFLATE-compressed first
+    /// and the ciphertext is stored last, because ciphertext is incompressible, so
+    /// compressing after encryption would save nothing.
+    pub fn new_encrypted_compressed(
+        path: impl AsRef<Path>,
+        password: &[u8],
+        salt: &[u8],
+    ) -> crate::wallet::Result<CompressedAdapter<EncryptedAdapter<Self>>> {
+        Ok(CompressedAdapter::new(EncryptedAdapter::new(Self::new(path)?, password, salt)))
+    }
+
     fn format_key(&self, key: &str) -> String {
         format!("{}-{}", self.key_prefix, key)
     }
@@ -0,0 +1,176 @@
+use super::sponge::{Mode, HASH_LENGTH};
+use failure::Error;
+
+const STATE_LENGTH: usize = 3 * HASH_LENGTH;
+
+/// Number of independent inputs that can be hashed in a single pass, one per bit of a
+/// `u64` lane.
+pub const MAX_BATCH_SIZE: usize = 64;
+
+/// A binary-coded-ternary variant of [`Curl`](super::curl::Curl) that hashes up to
+/// [`MAX_BATCH_SIZE`] independent inputs in one pass.
+///
+/// The state is held as two `[u64; STATE_LENGTH]` lanes (`low`, `high`): each trit
+/// position packs one bit per parallel slot and the value of slot `i` is encoded in the
+/// `(low_i, high_i)` bit pair as `-1 => (1, 0)`, `0 => (1, 1)`, `1 => (0, 1)`. The S-box
+/// — `state[i] = TRUTH_TABLE[scratch[p] + (scratch[q] << 2) + 5]` in the scalar path —
+/// becomes a pair of bitwise boolean expressions over the two lanes that reproduce
+/// exactly the same mapping for all 64 slots at once, so every lane is bit-for-bit
+/// identical to the scalar `Curl` output.
+#[derive(Clone, Copy)]
+pub struct BctCurl {
+    number_of_rounds: i32,
+    scratchpad_low: [u64; STATE_LENGTH],
+    scratchpad_high: [u64; STATE_LENGTH],
+    low: [u64; STATE_LENGTH],
+    high: [u64; STATE_LENGTH],
+}
+
+impl Default for BctCurl {
+    fn default() -> BctCurl {
+        BctCurl {
+            number_of_rounds: 81,
+            scratchpad_low: [0; STATE_LENGTH],
+            scratchpad_high: [0; STATE_LENGTH],
+            // The all-ones state encodes every slot as the trit 0, matching the zeroed
+            // scalar state.
+            low: [!0; STATE_LENGTH],
+            high: [!0; STATE_LENGTH],
+        }
+    }
+}
+
+impl BctCurl {
+    pub fn new(mode: Mode) -> Result<BctCurl, Error> {
+        let mut curl = BctCurl::default();
+        curl.number_of_rounds = match mode {
+            Mode::CURLP27 => 27,
+            Mode::CURLP81 => 81,
+            a => return Err(format_err!("Invalid mode: {}", a)),
+        };
+        Ok(curl)
+    }
+
+    fn set_trit(&mut self, index: usize, slot: usize, trit: i8) {
+        let mask = 1u64 << slot;
+        let (low, high) = match trit {
+            -1 => (true, false),
+            0 => (true, true),
+            _ => (false, true),
+        };
+        if low {
+            self.low[index] |= mask;
+        } else {
+            self.low[index] &= !mask;
+        }
+        if high {
+            self.high[index] |= mask;
+        } else {
+            self.high[index] &= !mask;
+        }
+    }
+
+    fn get_trit(&self, index: usize, slot: usize) -> i8 {
+        let low = (self.low[index] >> slot) & 1;
+        let high = (self.high[index] >> slot) & 1;
+        match (low, high) {
+            (1, 0) => -1,
+            (0, 1) => 1,
+            _ => 0,
+        }
+    }
+
+    fn transform(&mut self) {
+        let mut scratchpad_index = 0;
+        for _ in 0..self.number_of_rounds {
+            self.scratchpad_low.copy_from_slice(&self.low);
+            self.scratchpad_high.copy_from_slice(&self.high);
+            for state_index in 0..STATE_LENGTH {
+                let prev_scratchpad_index = scratchpad_index;
+                if scratchpad_index < 365 {
+                    scratchpad_index += 364;
+                } else {
+                    scratchpad_index -= 365;
+                }
+                let alpha = self.scratchpad_low[prev_scratchpad_index];
+                let beta = self.scratchpad_high[prev_scratchpad_index];
+                let gamma = self.scratchpad_high[scratchpad_index];
+                let delta = (alpha | !gamma) & (self.scratchpad_low[scratchpad_index] ^ beta);
+                self.low[state_index] = !delta;
+                self.high[state_index] = (alpha ^ gamma) | delta;
+            }
+        }
+    }
+}
+
+impl BctCurl {
+    /// Absorbs up to [`MAX_BATCH_SIZE`] independent inputs, broadcasting trit `j` of
+    /// input `slot` into bit `slot` of state position `j`. All inputs must share the same
+    /// length, a multiple of [`HASH_LENGTH`].
+    pub fn absorb(&mut self, inputs: &[&[i8]]) {
+        debug_assert!(inputs.len() <= MAX_BATCH_SIZE);
+        let length = inputs.first().map_or(0, |input| input.len());
+        let mut offset = 0;
+        while offset < length {
+            for (slot, input) in inputs.iter().enumerate() {
+                for (index, &trit) in input[offset..offset + HASH_LENGTH].iter().enumerate() {
+                    self.set_trit(index, slot, trit);
+                }
+            }
+            self.transform();
+            offset += HASH_LENGTH;
+        }
+    }
+
+    /// Emits one [`HASH_LENGTH`]-trit hash per input slot from a single sweep, in the same
+    /// slot order passed to [`absorb`](Self::absorb).
+    pub fn squeeze(&mut self, outputs: &mut [&mut [i8]]) {
+        debug_assert!(outputs.len() <= MAX_BATCH_SIZE);
+        for (slot, output) in outputs.iter_mut().enumerate() {
+            for (index, trit) in output.iter_mut().enumerate().take(HASH_LENGTH) {
+                *trit = self.get_trit(index, slot);
+            }
+        }
+        self.transform();
+    }
+
+    pub fn reset(&mut self) {
+        self.low = [!0; STATE_LENGTH];
+        self.high = [!0; STATE_LENGTH];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pow::curl::Curl;
+    use crate::pow::sponge::Sponge;
+    use crate::utils::converter;
+
+    const TRYTES: &str = "RSWWSFXPQJUBJROQBRQZWZXZJWMUBVIVMHPPTYSNW9YQIQQF9RCSJJCVZG9ZWITXNCSBBDHEEKDRBHVTWCZ9SZOOZHVBPCQNPKTWFNZAWGCZ9QDIMKRVINMIRZBPKRKQAIPGOHBTHTGYXTBJLSURDSPEOJ9UKJECUKCCPVIQQHDUYKVKISCEIEGVOQWRBAYXWGSJUTEVG9RPQLPTKYCRAJ9YNCUMDVDYDQCKRJOAPXCSUDAJGETALJINHEVNAARIPONBWXUOQUFGNOCUSSLYWKOZMZUKLNITZIFXFWQAYVJCVMDTRSHORGNSTKX9Z9DLWNHZSMNOYTU9AUCGYBVIITEPEKIXBCOFCMQPBGXYJKSHPXNUKFTXIJVYRFILAVXEWTUICZCYYPCEHNTK9SLGVL9RLAMYTAEPONCBHDXSEQZOXO9XCFUCPPMKEBR9IEJGQOPPILHFXHMIULJYXZJASQEGCQDVYFOM9ETXAGVMSCHHQLFPATWOSMZIDL9AHMSDCE9UENACG9OVFAEIPPQYBCLXDMXXA9UBJFQQBCYKETPNKHNOUKCSSYLWZDLKUARXNVKKKHNRBVSTVKQCZL9RY9BDTDTPUTFUBGRMSTOTXLWUHDMSGYRDSZLIPGQXIDMNCNBOAOI9WFUCXSRLJFIVTIPIAZUK9EDUJJ9B9YCJEZQQELLHVCWDNRH9FUXDGZRGOVXGOKORTCQQA9JXNROLETYCNLRMBGXBL9DQKMOAZCBJGWLNJLGRSTYBKLGFVRUF9QOPZVQFGMDJA9TBVGFJDBAHEVOLW9GNU9NICLCQJBOAJBAHHBZJGOFUCQMBGYQLCWNKSZPPBQMSJTJLM9GXOZHTNDLGIRCSIJAZTENQVQDHFSOQM9WVNWQQJNOPZMEISSCLOADMRNWALBBSLSWNCTOSNHNLWZBVCFIOGFPCPRKQSRGKFXGTWUSCPZSKQNLQJGKDLOXSBJMEHQPDZGSENUKWAHRNONDTBLHNAKGLOMCFYRCGMDOVANPFHMQRFCZIQHCGVORJJNYMTORDKPJPLA9LWAKAWXLIFEVLKHRKCDG9QPQCPGVKIVBENQJTJGZKFTNZHIMQISVBNLHAYSSVJKTIELGTETKPVRQXNAPWOBGQGFRMMK9UQDWJHSQMYQQTCBMVQKUVGJEAGTEQDN9TCRRAZHDPSPIYVNKPGJSJZASZQBM9WXEDWGAOQPPZFLAMZLEZGXPYSOJRWL9ZH9NOJTUKXNTCRRDO9GKULXBAVDRIZBOKJYVJUSHIX9F9O9ACYCAHUKBIEPVZWVJAJGSDQNZNWLIWVSKFJUMOYDMVUFLUXT9CEQEVRFBJVPCTJQCORM9JHLYFSMUVMFDXZFNCUFZZIKREIUIHUSHRPPOUKGFKWX9COXBAZMQBBFRFIBGEAVKBWKNTBMLPHLOUYOXPIQIZQWGOVUWQABTJT9ZZPNBABQFYRCQLXDHDEX9PULVTCQLWPTJLRSVZQEEYVBVY9KCNEZXQLEGADSTJBYOXEVGVTUFKNCNWMEDKDUMTKCMRPGKDCCBDHDVVSMPOPUBZOMZTXJSQNVVGXNPPBVSBL9WWXWQNMHRMQFEQYKWNCSW9URI9FYPT9UZMAFMMGUKFYTWPCQKVJ9DIHRJFMXRZUGI9TMTFUQHGXNBITDSORZORQIAMKY9VRYKLEHNRNFSEFBHF9KXIQAEZEJNQOENJVMWLMHI9GNZPXYUIFAJIVCLAGKUZIKTJKGNQVTXJORWIQDHUPBBPPYOUPFAABBVMMYATXERQHPECDVYGWDGXFJKOMOBXKRZD9MCQ9LGDGGGMYGUAFGMQTUHZOAPLKPNPCIKUNEMQIZOCM9COAOMZSJ9GVWZBZYXMCNALENZ9PRYMHENPWGKX9ULUIGJUJRKFJPBTTHCRZQKEAHT9DC9GSWQEGDTZFHACZMLFYDVOWZADBNMEM9XXEOMHCNJMDSUAJRQTBUWKJF9RZHK9ACGUNI9URFIHLXBXCEODONPXBSCWP9WNAEYNALKQHGULUQGAFL9LB9NBLLCACLQFGQMXRHGBTMI9YKAJKVELRWWKJAPKMSYMJTDYMZ9PJEEYIRXRMMFLRSFSHIXUL9NEJABLRUGHJFL9RASMSKOI9VCFRZ9GWTMODUUESIJBHWWHZYCLDENBFSJQPIOYC9MBGOOXSWEMLVU9L9WJXKZKVDBDMFSVHHISSSNILUMWULMVMESQUIHDGBDXROXGH9MTNFSLWJZRAPOKKRGXAAQBFPYPAAXLSTMNSNDTTJQSDQORNJS9BBGQ9KQJZYPAQ9JYQZJ9B9KQDAXUACZWRUNGMBOQLQZUHFNCKVQGORRZGAHES9PWJUKZWUJSBMNZFILBNBQQKLXITCTQDDBV9UDAOQOUPWMXTXWFWVMCXIXLRMRWMAYYQJPCEAAOFEOGZQMEDAGYGCTKUJBS9AGEXJAFHWWDZRYEN9DN9HVCMLFURISLYSWKXHJKXMHUWZXUQARMYPGKRKQMHVR9JEYXJRPNZINYNCGZHHUNHBAIJHLYZIZGGIDFWVNXZQADLEDJFTIUTQWCQSX9QNGUZXGXJYUUTFSZPQKXBA9DFRQRLTLUJENKESDGTZRGRSLTNYTITXRXRGVLWBTEWPJXZYLGHLQBAVYVOSABIVTQYQM9FIQKCBRRUEMVVTMERLWOK";
+
+    #[test]
+    fn test_bct_curl_matches_scalar() {
+        let size = 8019;
+        let in_trits = converter::trits_from_string(TRYTES);
+
+        // Reference hash from the scalar Curl.
+        let mut scalar = Curl::default();
+        let mut expected = vec![0; HASH_LENGTH];
+        scalar.absorb(&mut in_trits.clone()[0..size]);
+        scalar.squeeze(&mut expected);
+
+        // Broadcast the same input into every slot and cross-check each lane.
+        let inputs: Vec<&[i8]> = vec![&in_trits[0..size]; MAX_BATCH_SIZE];
+        let mut curl = BctCurl::default();
+        curl.absorb(&inputs);
+
+        let mut hashes = vec![vec![0i8; HASH_LENGTH]; MAX_BATCH_SIZE];
+        let mut outputs: Vec<&mut [i8]> = hashes.iter_mut().map(|h| h.as_mut_slice()).collect();
+        curl.squeeze(&mut outputs);
+
+        for hash in &hashes {
+            assert_eq!(expected, *hash);
+        }
+    }
+}
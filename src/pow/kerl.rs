@@ -0,0 +1,144 @@
+use super::sponge::{Sponge, HASH_LENGTH};
+use tiny_keccak::Keccak;
+
+const BYTE_HASH_LENGTH: usize = 48;
+
+/// A [`Sponge`] backed by Keccak-384, providing the collision-resistant hashing that
+/// `Curl` lacks and that address and signature generation require.
+///
+/// Each 243-trit chunk is read as the balanced-ternary big integer it represents (with
+/// the 243rd trit forced to zero so the value fits in 48 bytes), encoded big-endian and
+/// fed into Keccak-384. On `squeeze` the 48-byte digest is decoded back to 243 trits and
+/// every byte of the internal state is negated before the next squeeze — the standard
+/// Kerl chaining step. `HASH_LENGTH` stays 243, so `Kerl` is drop-in wherever a `Sponge`
+/// is expected.
+#[derive(Clone)]
+pub struct Kerl {
+    keccak: Keccak,
+    byte_state: [u8; BYTE_HASH_LENGTH],
+}
+
+impl Default for Kerl {
+    fn default() -> Kerl {
+        Kerl {
+            keccak: Keccak::new_keccak384(),
+            byte_state: [0; BYTE_HASH_LENGTH],
+        }
+    }
+}
+
+/// Encodes the first `HASH_LENGTH - 1` trits of `trits` as the big-endian two's-complement
+/// byte representation of the balanced-ternary integer they form; the 243rd trit is
+/// treated as zero to keep the value within 48 bytes.
+fn trits_to_bytes(trits: &[i8], bytes: &mut [u8; BYTE_HASH_LENGTH]) {
+    *bytes = [0; BYTE_HASH_LENGTH];
+    for &trit in trits[..HASH_LENGTH - 1].iter().rev() {
+        // bytes = bytes * 3 + trit, carrying through the whole two's-complement number.
+        let mut carry = trit as i32;
+        for byte in bytes.iter_mut().rev() {
+            let v = *byte as i32 * 3 + carry;
+            *byte = (v & 0xFF) as u8;
+            carry = v >> 8;
+        }
+    }
+}
+
+/// Reverses [`trits_to_bytes`], writing `HASH_LENGTH` trits into `trits` (the last one is
+/// always zero).
+fn bytes_to_trits(bytes: &[u8; BYTE_HASH_LENGTH], trits: &mut [i8]) {
+    let negative = bytes[0] & 0x80 != 0;
+
+    // Work on the magnitude; the balanced-ternary expansion of a negative value is the
+    // trit-wise negation of its magnitude's expansion.
+    let mut magnitude = *bytes;
+    if negative {
+        let mut carry = 1u32;
+        for byte in magnitude.iter_mut().rev() {
+            let v = (!*byte) as u32 + carry;
+            *byte = (v & 0xFF) as u8;
+            carry = v >> 8;
+        }
+    }
+
+    for trit in trits[..HASH_LENGTH - 1].iter_mut() {
+        // Long-divide the magnitude by 3, most significant byte first.
+        let mut remainder = 0u32;
+        for byte in magnitude.iter_mut() {
+            let cur = (remainder << 8) | *byte as u32;
+            *byte = (cur / 3) as u8;
+            remainder = cur % 3;
+        }
+        *trit = match remainder {
+            0 => 0,
+            1 => 1,
+            // A remainder of 2 balances to -1, which rounds the quotient up by one.
+            _ => {
+                let mut carry = 1u32;
+                for byte in magnitude.iter_mut().rev() {
+                    let v = *byte as u32 + carry;
+                    *byte = (v & 0xFF) as u8;
+                    carry = v >> 8;
+                    if carry == 0 {
+                        break;
+                    }
+                }
+                -1
+            }
+        };
+        if negative {
+            *trit = -*trit;
+        }
+    }
+    trits[HASH_LENGTH - 1] = 0;
+}
+
+impl Sponge for Kerl {
+    fn absorb(&mut self, trits: &mut [i8]) {
+        for chunk in trits.chunks(HASH_LENGTH) {
+            trits_to_bytes(chunk, &mut self.byte_state);
+            self.keccak.update(&self.byte_state);
+        }
+    }
+
+    fn squeeze(&mut self, out: &mut [i8]) {
+        let mut trits = [0i8; HASH_LENGTH];
+        for chunk in out.chunks_mut(HASH_LENGTH) {
+            self.keccak.clone().finalize(&mut self.byte_state);
+            bytes_to_trits(&self.byte_state, &mut trits);
+            chunk.clone_from_slice(&trits[..chunk.len()]);
+
+            // Negate the byte state and feed it back in to chain the next squeeze.
+            for byte in self.byte_state.iter_mut() {
+                *byte ^= 0xFF;
+            }
+            self.keccak = Keccak::new_keccak384();
+            self.keccak.update(&self.byte_state);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.keccak = Keccak::new_keccak384();
+        self.byte_state = [0; BYTE_HASH_LENGTH];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::converter;
+
+    const TRYTES: &str = "EMIDYNHBWMBCXVDEFOFWINXTERALUKYYPPHKP9JJFGJEIUY9MUDVNFZHMMWZUYUSWAIOWEVTHNWMHANBH";
+    const HASH: &str =
+        "EJEAOOZYSAWFPZQESYDHZCGYNSTWXUMVJOVDWUNZJXDGWCLUFGIMZRMGCAZGKNPLBRLGUNYWKLJTYEAQX";
+
+    #[test]
+    fn test_kerl_works() {
+        let mut in_trits = converter::trits_from_string(TRYTES);
+        let mut hash_trits = vec![0; HASH_LENGTH];
+        let mut kerl = Kerl::default();
+        kerl.absorb(&mut in_trits);
+        kerl.squeeze(&mut hash_trits);
+        let out_trytes = converter::trytes(&hash_trits);
+        assert_eq!(HASH, out_trytes);
+    }
+}